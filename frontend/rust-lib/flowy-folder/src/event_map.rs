@@ -0,0 +1,25 @@
+use crate::{entities::app::*, errors::*};
+use folder_model::AppRevision;
+
+/// Thin seam over the current session, so folder services don't reach into
+/// auth/session storage directly. Each client surface (desktop, mobile,
+/// web) implements this against its own signed-in user.
+pub(crate) trait WorkspaceUser: Send + Sync {
+  fn token(&self) -> FlowyResult<String>;
+
+  /// Every workspace the current user belongs to. Background reconciliation
+  /// (see `ScrubWorker`) walks all of them rather than assuming a single
+  /// active workspace.
+  fn workspace_ids(&self) -> FlowyResult<Vec<String>>;
+}
+
+/// The v1 cloud API surface the folder service talks to for app sync.
+#[async_trait::async_trait]
+pub(crate) trait FolderCouldServiceV1: Send + Sync {
+  async fn create_app(&self, token: &str, params: CreateAppParams) -> FlowyResult<AppRevision>;
+  async fn update_app(&self, token: &str, params: UpdateAppParams) -> FlowyResult<()>;
+
+  /// The server's current view of every app in `workspace_id`, so the drift
+  /// scrub can reconcile local state against it.
+  async fn read_workspace_apps(&self, token: &str, workspace_id: &str) -> FlowyResult<Vec<AppRevision>>;
+}
@@ -0,0 +1,453 @@
+use crate::{
+  errors::*,
+  services::app::controller::{AppOutboxRow, AppOutboxStatus, ScrubState, OUTBOX_STALE_RUNNING_SECS},
+};
+use folder_model::AppRevision;
+use std::{cell::RefCell, collections::HashMap, sync::Mutex};
+
+/// A partial update to a single app's persisted fields, as submitted to
+/// [FolderPersistenceTransaction::update_app]. `None` leaves the stored
+/// value for that field unchanged, so callers only need to carry the fields
+/// the user actually edited.
+#[derive(Clone, Debug)]
+pub(crate) struct AppChangeset {
+  pub(crate) id: String,
+  pub(crate) name: Option<String>,
+}
+
+impl AppChangeset {
+  pub(crate) fn new(params: UpdateAppParams) -> Self {
+    Self {
+      id: params.app_id,
+      name: params.name,
+    }
+  }
+}
+
+/// A single open unit of work against the embedded folder store. All
+/// methods are synchronous: callers reach this through
+/// [FolderPersistence::begin_transaction], which already holds the store's
+/// lock for the duration of the closure, so there's nothing here to `.await`
+/// on.
+pub(crate) trait FolderPersistenceTransaction {
+  fn create_app(&self, app: AppRevision) -> FlowyResult<()>;
+  fn read_app(&self, app_id: &str) -> FlowyResult<AppRevision>;
+  fn update_app(&self, changeset: AppChangeset) -> FlowyResult<()>;
+  fn move_app(&self, app_id: &str, from: usize, to: usize) -> FlowyResult<()>;
+  /// Removes the app and returns what was removed, so callers can read its
+  /// `workspace_id` for notification purposes without a second lookup.
+  fn delete_app(&self, app_id: &str) -> FlowyResult<AppRevision>;
+  fn read_workspace_apps(&self, workspace_id: &str) -> FlowyResult<Vec<AppRevision>>;
+
+  /// Durable outbox used by [crate::services::app::controller::OutboxDrainWorker]
+  /// so pending cloud mutations survive a restart.
+  fn enqueue_outbox_op(&self, row: AppOutboxRow) -> FlowyResult<()>;
+  /// Atomically claims every `New` row whose `next_attempt_at` has passed,
+  /// marking them `Running` so a concurrent drain pass won't double-send
+  /// them, and returns the claimed rows.
+  fn pop_due_outbox_ops(&self, now: i64) -> FlowyResult<Vec<AppOutboxRow>>;
+  fn delete_outbox_op(&self, id: &str) -> FlowyResult<()>;
+  fn update_outbox_op(&self, row: AppOutboxRow) -> FlowyResult<()>;
+  /// Whether an outbox row is still pending for `app_id`, keyed the same
+  /// way `enqueue_outbox_op` keys its rows (by the app id the op mutates).
+  /// Used by [crate::services::app::controller::ScrubWorker] to avoid
+  /// deleting a local app that was just created offline and whose
+  /// `CreateApp` outbox row hasn't drained to the server yet.
+  fn has_pending_outbox_op(&self, app_id: &str) -> FlowyResult<bool>;
+
+  /// Persists the drift scrub's most recent pass, read back by diagnostics.
+  fn write_scrub_state(&self, state: ScrubState) -> FlowyResult<()>;
+  fn read_scrub_state(&self) -> FlowyResult<ScrubState>;
+}
+
+/// Backing storage for the embedded transaction below. Fields are
+/// `RefCell`s rather than plain values because every method on
+/// [FolderPersistenceTransaction] takes `&self` (the trait is shared across
+/// call sites that only ever hold a shared reference into an open
+/// transaction) while still needing to mutate; [FolderPersistence] is the
+/// only thing that ever hands out a transaction, and it does so from behind
+/// its own `Mutex`, so there's always at most one live borrow of these
+/// `RefCell`s at a time.
+#[derive(Default)]
+struct FolderPersistenceStore {
+  apps: RefCell<HashMap<String, AppRevision>>,
+  workspace_order: RefCell<HashMap<String, Vec<String>>>,
+  outbox: RefCell<HashMap<String, AppOutboxRow>>,
+  scrub_state: RefCell<Option<ScrubState>>,
+}
+
+/// A point-in-time copy of every map in [FolderPersistenceStore], taken
+/// before a transaction's closure runs so [FolderPersistence::begin_transaction]
+/// can put the store back exactly as it found it if the closure returns
+/// `Err`. Plain clones rather than a diff/journal because the store is
+/// small and every field is already cheaply cloneable.
+struct FolderPersistenceSnapshot {
+  apps: HashMap<String, AppRevision>,
+  workspace_order: HashMap<String, Vec<String>>,
+  outbox: HashMap<String, AppOutboxRow>,
+  scrub_state: Option<ScrubState>,
+}
+
+impl FolderPersistenceStore {
+  fn snapshot(&self) -> FolderPersistenceSnapshot {
+    FolderPersistenceSnapshot {
+      apps: self.apps.borrow().clone(),
+      workspace_order: self.workspace_order.borrow().clone(),
+      outbox: self.outbox.borrow().clone(),
+      scrub_state: self.scrub_state.borrow().clone(),
+    }
+  }
+
+  fn restore(&self, snapshot: FolderPersistenceSnapshot) {
+    *self.apps.borrow_mut() = snapshot.apps;
+    *self.workspace_order.borrow_mut() = snapshot.workspace_order;
+    *self.outbox.borrow_mut() = snapshot.outbox;
+    *self.scrub_state.borrow_mut() = snapshot.scrub_state;
+  }
+}
+
+struct EmbeddedTransaction<'a> {
+  store: &'a FolderPersistenceStore,
+}
+
+impl<'a> FolderPersistenceTransaction for EmbeddedTransaction<'a> {
+  fn create_app(&self, app: AppRevision) -> FlowyResult<()> {
+    let workspace_id = app.workspace_id.clone();
+    let id = app.id.clone();
+    self.store.apps.borrow_mut().insert(id.clone(), app);
+    self
+      .store
+      .workspace_order
+      .borrow_mut()
+      .entry(workspace_id)
+      .or_default()
+      .push(id);
+    Ok(())
+  }
+
+  fn read_app(&self, app_id: &str) -> FlowyResult<AppRevision> {
+    self
+      .store
+      .apps
+      .borrow()
+      .get(app_id)
+      .cloned()
+      .ok_or_else(|| FlowyError::internal().context(format!("app {} not found", app_id)))
+  }
+
+  fn update_app(&self, changeset: AppChangeset) -> FlowyResult<()> {
+    let mut apps = self.store.apps.borrow_mut();
+    let app = apps
+      .get_mut(&changeset.id)
+      .ok_or_else(|| FlowyError::internal().context(format!("app {} not found", changeset.id)))?;
+    if let Some(name) = changeset.name {
+      app.name = name;
+    }
+    Ok(())
+  }
+
+  fn move_app(&self, app_id: &str, from: usize, to: usize) -> FlowyResult<()> {
+    let workspace_id = self
+      .store
+      .apps
+      .borrow()
+      .get(app_id)
+      .map(|app| app.workspace_id.clone())
+      .ok_or_else(|| FlowyError::internal().context(format!("app {} not found", app_id)))?;
+    let mut order = self.store.workspace_order.borrow_mut();
+    let ids = order.entry(workspace_id).or_default();
+    if from >= ids.len() || to >= ids.len() {
+      return Err(FlowyError::internal().context(format!(
+        "move_app({}, {}, {}) out of bounds for {} apps",
+        app_id,
+        from,
+        to,
+        ids.len()
+      )));
+    }
+    let id = ids.remove(from);
+    ids.insert(to, id);
+    Ok(())
+  }
+
+  fn delete_app(&self, app_id: &str) -> FlowyResult<AppRevision> {
+    let app = self
+      .store
+      .apps
+      .borrow_mut()
+      .remove(app_id)
+      .ok_or_else(|| FlowyError::internal().context(format!("app {} not found", app_id)))?;
+    if let Some(ids) = self.store.workspace_order.borrow_mut().get_mut(&app.workspace_id) {
+      ids.retain(|id| id != app_id);
+    }
+    Ok(app)
+  }
+
+  fn read_workspace_apps(&self, workspace_id: &str) -> FlowyResult<Vec<AppRevision>> {
+    let order = self.store.workspace_order.borrow();
+    let apps = self.store.apps.borrow();
+    Ok(
+      order
+        .get(workspace_id)
+        .map(|ids| ids.iter().filter_map(|id| apps.get(id).cloned()).collect())
+        .unwrap_or_default(),
+    )
+  }
+
+  fn enqueue_outbox_op(&self, row: AppOutboxRow) -> FlowyResult<()> {
+    self.store.outbox.borrow_mut().insert(row.id.clone(), row);
+    Ok(())
+  }
+
+  fn pop_due_outbox_ops(&self, now: i64) -> FlowyResult<Vec<AppOutboxRow>> {
+    let mut outbox = self.store.outbox.borrow_mut();
+    // Reclaim rows left `Running` by a process that died between popping
+    // them and resolving them (delete/reschedule), so they aren't stuck
+    // forever — `pop_due_outbox_ops` only ever selects `New` rows below.
+    for row in outbox.values_mut() {
+      if row.status == AppOutboxStatus::Running
+        && row.claimed_at.map_or(true, |claimed_at| now - claimed_at > OUTBOX_STALE_RUNNING_SECS)
+      {
+        row.status = AppOutboxStatus::New;
+        row.claimed_at = None;
+      }
+    }
+
+    let due_ids: Vec<String> = outbox
+      .values()
+      .filter(|row| row.status == AppOutboxStatus::New && row.next_attempt_at <= now)
+      .map(|row| row.id.clone())
+      .collect();
+    let mut due_rows = Vec::with_capacity(due_ids.len());
+    for id in due_ids {
+      if let Some(row) = outbox.get_mut(&id) {
+        row.status = AppOutboxStatus::Running;
+        row.claimed_at = Some(now);
+        due_rows.push(row.clone());
+      }
+    }
+    Ok(due_rows)
+  }
+
+  fn delete_outbox_op(&self, id: &str) -> FlowyResult<()> {
+    self.store.outbox.borrow_mut().remove(id);
+    Ok(())
+  }
+
+  fn update_outbox_op(&self, row: AppOutboxRow) -> FlowyResult<()> {
+    self.store.outbox.borrow_mut().insert(row.id.clone(), row);
+    Ok(())
+  }
+
+  fn has_pending_outbox_op(&self, app_id: &str) -> FlowyResult<bool> {
+    Ok(self.store.outbox.borrow().contains_key(app_id))
+  }
+
+  fn write_scrub_state(&self, state: ScrubState) -> FlowyResult<()> {
+    *self.store.scrub_state.borrow_mut() = Some(state);
+    Ok(())
+  }
+
+  fn read_scrub_state(&self) -> FlowyResult<ScrubState> {
+    Ok(self.store.scrub_state.borrow().clone().unwrap_or_default())
+  }
+}
+
+/// The embedded, per-device folder store. Owns every app, the outbox, and
+/// the drift-scrub checkpoint behind a single lock, and hands out a
+/// [FolderPersistenceTransaction] for the duration of a closure passed to
+/// [begin_transaction](Self::begin_transaction) rather than exposing the
+/// lock directly, so every read/write the rest of this crate does goes
+/// through the same critical section.
+#[derive(Default)]
+pub(crate) struct FolderPersistence {
+  store: Mutex<FolderPersistenceStore>,
+}
+
+impl FolderPersistence {
+  pub(crate) fn new() -> Self {
+    Self::default()
+  }
+
+  /// Runs `f` against a transaction over the embedded store, holding the
+  /// store's lock for `f`'s duration so everything it does is atomic with
+  /// respect to every other `begin_transaction` call. Takes a snapshot of
+  /// the store before `f` runs and restores it verbatim if `f` returns
+  /// `Err`, so a partially-applied batch never leaves its earlier writes
+  /// behind — the embedded store has no on-disk log to roll back, so a
+  /// restored in-memory snapshot is the only way to honor that.
+  pub(crate) async fn begin_transaction<'f, F, O>(&self, f: F) -> FlowyResult<O>
+  where
+    F: FnOnce(&dyn FolderPersistenceTransaction) -> FlowyResult<O> + Send + 'f,
+    O: Send,
+  {
+    let guard = self.store.lock().unwrap();
+    let snapshot = guard.snapshot();
+    let transaction = EmbeddedTransaction { store: &guard };
+    let result = f(&transaction);
+    if result.is_err() {
+      guard.restore(snapshot);
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::services::app::controller::AppOutboxOpKind;
+  use folder_model::AppRevision;
+
+  fn test_app(id: &str, workspace_id: &str, name: &str) -> AppRevision {
+    AppRevision {
+      id: id.to_owned(),
+      workspace_id: workspace_id.to_owned(),
+      name: name.to_owned(),
+      ..Default::default()
+    }
+  }
+
+  // Drives the real embedded store (`FolderPersistence`/`EmbeddedTransaction`),
+  // not the in-memory `TestFolderStore` double in `app::controller`'s test
+  // module — that double's rollback was never wired up to this code, so it
+  // couldn't have caught `begin_transaction` shipping without rollback.
+  #[tokio::test]
+  async fn begin_transaction_rolls_back_on_failure() {
+    let persistence = FolderPersistence::new();
+    persistence
+      .begin_transaction(|transaction| transaction.create_app(test_app("a", "ws1", "A")))
+      .await
+      .unwrap();
+
+    let result = persistence
+      .begin_transaction(|transaction| {
+        transaction.update_app(AppChangeset {
+          id: "a".to_owned(),
+          name: Some("A2".to_owned()),
+        })?;
+        // Fails because "missing" was never created, which should abort the
+        // whole unit and undo the update above.
+        transaction.delete_app("missing")?;
+        Ok(())
+      })
+      .await;
+    assert!(result.is_err());
+
+    let app = persistence.begin_transaction(|transaction| transaction.read_app("a")).await.unwrap();
+    assert_eq!(app.name, "A", "update from the failed batch must not have persisted");
+  }
+
+  #[tokio::test]
+  async fn begin_transaction_rolls_back_outbox_enqueue_on_failure() {
+    let persistence = FolderPersistence::new();
+    persistence
+      .begin_transaction(|transaction| transaction.create_app(test_app("a", "ws1", "A")))
+      .await
+      .unwrap();
+
+    let result = persistence
+      .begin_transaction(|transaction| {
+        transaction.enqueue_outbox_op(AppOutboxRow {
+          id: "a".to_owned(),
+          op_kind: AppOutboxOpKind::UpdateApp,
+          payload: Vec::new(),
+          status: AppOutboxStatus::New,
+          attempts: 0,
+          next_attempt_at: 0,
+          claimed_at: None,
+        })?;
+        transaction.delete_app("missing")?;
+        Ok(())
+      })
+      .await;
+    assert!(result.is_err());
+
+    let now = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .unwrap()
+      .as_secs() as i64;
+    let due = persistence
+      .begin_transaction(|transaction| transaction.pop_due_outbox_ops(now))
+      .await
+      .unwrap();
+    assert!(due.is_empty(), "outbox row from the failed batch must not have persisted");
+  }
+
+  // Backs `ScrubWorker::run_iteration`'s decision to leave a server-absent
+  // app alone while its create is still waiting in the outbox.
+  #[tokio::test]
+  async fn has_pending_outbox_op_reflects_a_still_queued_row() {
+    let persistence = FolderPersistence::new();
+    persistence
+      .begin_transaction(|transaction| transaction.create_app(test_app("a", "ws1", "A")))
+      .await
+      .unwrap();
+    assert!(!persistence
+      .begin_transaction(|transaction| transaction.has_pending_outbox_op("a"))
+      .await
+      .unwrap());
+
+    persistence
+      .begin_transaction(|transaction| {
+        transaction.enqueue_outbox_op(AppOutboxRow {
+          id: "a".to_owned(),
+          op_kind: AppOutboxOpKind::CreateApp,
+          payload: Vec::new(),
+          status: AppOutboxStatus::New,
+          attempts: 0,
+          next_attempt_at: 0,
+          claimed_at: None,
+        })
+      })
+      .await
+      .unwrap();
+    assert!(persistence
+      .begin_transaction(|transaction| transaction.has_pending_outbox_op("a"))
+      .await
+      .unwrap());
+
+    persistence
+      .begin_transaction(|transaction| transaction.delete_outbox_op("a"))
+      .await
+      .unwrap();
+    assert!(!persistence
+      .begin_transaction(|transaction| transaction.has_pending_outbox_op("a"))
+      .await
+      .unwrap());
+  }
+
+  #[tokio::test]
+  async fn pop_due_outbox_ops_reclaims_a_stale_running_row() {
+    let persistence = FolderPersistence::new();
+    persistence
+      .begin_transaction(|transaction| {
+        transaction.enqueue_outbox_op(AppOutboxRow {
+          id: "a".to_owned(),
+          op_kind: AppOutboxOpKind::CreateApp,
+          payload: Vec::new(),
+          status: AppOutboxStatus::Running,
+          attempts: 0,
+          next_attempt_at: 0,
+          claimed_at: Some(0),
+        })
+      })
+      .await
+      .unwrap();
+
+    // Not stale yet: still well within the staleness window.
+    let due = persistence
+      .begin_transaction(|transaction| transaction.pop_due_outbox_ops(OUTBOX_STALE_RUNNING_SECS - 1))
+      .await
+      .unwrap();
+    assert!(due.is_empty(), "a Running row that isn't stale yet must not be reclaimed");
+
+    // Stale: the row's claim is older than the staleness window, as if the
+    // process that popped it died before resolving it.
+    let due = persistence
+      .begin_transaction(|transaction| transaction.pop_due_outbox_ops(OUTBOX_STALE_RUNNING_SECS + 1))
+      .await
+      .unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, "a");
+  }
+}